@@ -1,24 +1,35 @@
-use cosmwasm_std::{Addr, DepsMut, Response, StdResult};
+use cosmwasm_std::{Addr, DepsMut, Response};
 use cw2::set_contract_version;
 
-use crate::state::{AUCTIONS, OWNERS};
+use crate::{
+    error::ContractError,
+    state::{AUCTION_SEQ, FEE_BPS, OWNERS},
+};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub fn instantiate(deps: DepsMut, sender: Addr) -> StdResult<Response> {
+//Fee is expressed in basis points (1/100th of a percent), so it can never exceed 100%.
+const MAX_FEE_BPS: u64 = 10_000;
+
+pub fn instantiate(deps: DepsMut, sender: Addr, fee_bps: u64) -> Result<Response, ContractError> {
     //Set name and version of auction house contract
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     deps.api.addr_validate(&sender.clone().into_string())?;
 
+    if fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFee { fee_bps });
+    }
+
     let mut owners = OWNERS.load(deps.storage)?;
 
     owners.push(sender.clone());
     //The instantiation of this contract will also be the initial owner of it.
     OWNERS.save(deps.storage, &owners)?;
 
-    AUCTIONS.save(deps.storage, &Vec::new())?;
+    AUCTION_SEQ.save(deps.storage, &0)?;
+    FEE_BPS.save(deps.storage, &fee_bps)?;
 
     let resp = Response::new()
         .add_attribute("action", "Instantiating Action House")
@@ -32,21 +43,79 @@ pub mod query {
         types::rewards::{ContractMetadataResponse, RewardsRecordsResponse},
         ArchwayQuery, PageRequest,
     };
-    use cosmwasm_std::{Deps, Env, StdResult};
+    use cosmwasm_std::{Deps, Env, Order, StdResult};
+    use cw_storage_plus::Bound;
     use cw_utils::NativeBalance;
 
     use crate::{
-        msg::{OpenAuctionsResp, OutstandingRewardsResponse},
-        state::AUCTIONS,
+        msg::{
+            CurrentPriceResponse, OpenAuctionsResp, OutstandingRewardsResponse, SellerAuctionsResp,
+        },
+        state::{auctions, required_bid},
     };
 
-    //We return the current auctions that are still open and/or unclaimed.
-    pub fn open_auctions(deps: Deps<ArchwayQuery>) -> StdResult<OpenAuctionsResp> {
-        let auctions = AUCTIONS.load(deps.storage)?;
+    const DEFAULT_PAGE_LIMIT: u32 = 30;
+    const MAX_PAGE_LIMIT: u32 = 100;
+
+    //We return the current auctions that are still open and/or unclaimed, paged by auction
+    //id through `start_after` and `limit`.
+    pub fn open_auctions(
+        deps: Deps<ArchwayQuery>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<OpenAuctionsResp> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let auctions = auctions()
+            .idx
+            .open
+            .prefix(1u8)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, auction)| auction))
+            .collect::<StdResult<Vec<_>>>()?;
 
         Ok(OpenAuctionsResp { auctions })
     }
 
+    //All auctions (open or closed) created by `seller`, paged by auction id the same way
+    //`open_auctions` pages the open/closed index.
+    pub fn auctions_by_seller(
+        deps: Deps<ArchwayQuery>,
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<SellerAuctionsResp> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let auctions = auctions()
+            .idx
+            .seller
+            .prefix(seller)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, auction)| auction))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(SellerAuctionsResp { auctions })
+    }
+
+    //The bid an auction currently requires to win right now: the live decaying ask for a
+    //Dutch lot, or for an English one the reserve price until the first bid and the
+    //next-increment price after it.
+    pub fn current_price(
+        deps: Deps<ArchwayQuery>,
+        env: Env,
+        auction_id: u64,
+    ) -> StdResult<CurrentPriceResponse> {
+        let auction = auctions().load(deps.storage, auction_id)?;
+        let price = required_bid(&auction, env.block.time)?;
+
+        Ok(CurrentPriceResponse { price })
+    }
+
     //We get the owner address and rewards address
     pub fn contract_metadata(
         deps: Deps<ArchwayQuery>,
@@ -84,13 +153,492 @@ pub mod query {
             total_records,
         })
     }
+
+    #[cfg(test)]
+    mod tests {
+        use std::marker::PhantomData;
+
+        use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+        use cosmwasm_std::{Addr, OwnedDeps, Timestamp, Uint128};
+
+        use super::*;
+        use crate::state::{Auction, AuctionItem, AuctionKind, BidAsset};
+
+        fn mock_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier, ArchwayQuery> {
+            OwnedDeps {
+                storage: MockStorage::default(),
+                api: MockApi::default(),
+                querier: MockQuerier::default(),
+                custom_query_type: PhantomData,
+            }
+        }
+
+        fn seed(storage: &mut dyn cosmwasm_std::Storage, id: u64, seller: &str, closed: bool) {
+            let auction = Auction {
+                id,
+                seller: Addr::unchecked(seller),
+                item: AuctionItem::Native {
+                    item_id: format!("lot-{id}"),
+                },
+                bid_asset: BidAsset::Native {
+                    denom: "uarch".to_string(),
+                },
+                kind: AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                end_time: Timestamp::from_seconds(1_000),
+                high_bidder: None,
+                high_bid: None,
+                closed,
+            };
+            auctions().save(storage, id, &auction).unwrap();
+        }
+
+        #[test]
+        fn open_auctions_excludes_closed_and_pages_by_start_after() {
+            let mut deps = mock_deps();
+            seed(deps.as_mut().storage, 1, "seller-a", false);
+            seed(deps.as_mut().storage, 2, "seller-a", true);
+            seed(deps.as_mut().storage, 3, "seller-b", false);
+            seed(deps.as_mut().storage, 4, "seller-b", false);
+
+            let page1 = open_auctions(deps.as_ref(), None, Some(2)).unwrap();
+            let ids: Vec<u64> = page1.auctions.iter().map(|a| a.id).collect();
+            assert_eq!(ids, vec![1, 3]);
+
+            let page2 = open_auctions(deps.as_ref(), Some(3), Some(2)).unwrap();
+            let ids: Vec<u64> = page2.auctions.iter().map(|a| a.id).collect();
+            assert_eq!(ids, vec![4]);
+        }
+
+        #[test]
+        fn auctions_by_seller_returns_only_that_sellers_lots() {
+            let mut deps = mock_deps();
+            seed(deps.as_mut().storage, 1, "seller-a", false);
+            seed(deps.as_mut().storage, 2, "seller-a", true);
+            seed(deps.as_mut().storage, 3, "seller-b", false);
+
+            let resp =
+                auctions_by_seller(deps.as_ref(), "seller-a".to_string(), None, None).unwrap();
+            let ids: Vec<u64> = resp.auctions.iter().map(|a| a.id).collect();
+            assert_eq!(ids, vec![1, 2]);
+        }
+    }
 }
 
 pub mod exec {
     use archway_bindings::{ArchwayMsg, ArchwayQuery, ArchwayResult};
-    use cosmwasm_std::{Addr, DepsMut, Response};
+    use cosmwasm_std::{
+        from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo,
+        Response, StdResult, Uint128, WasmMsg,
+    };
+    use cw20::Cw20ReceiveMsg;
+    use cw721::Cw721ReceiveMsg;
+    use serde::Serialize;
+
+    use crate::{
+        error::ContractError,
+        msg::{CreateAuctionMsg, PlaceBidMsg},
+        state::{
+            auctions, required_bid, Auction, AuctionItem, AuctionKind, BidAsset, AUCTION_SEQ,
+            FEE_BPS, OWNERS,
+        },
+    };
+
+    //Mirrors the `TransferNft` variant of the standard cw721 `ExecuteMsg`; that's the only
+    //message this contract ever needs to send to a cw721 contract.
+    #[derive(Serialize, Clone, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum Cw721ExecuteMsg {
+        TransferNft { recipient: String, token_id: String },
+    }
+
+    //Mirrors the `Transfer` variant of the standard cw20 `ExecuteMsg`; that's the only
+    //message this contract ever needs to send to a cw20 contract.
+    #[derive(Serialize, Clone, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum Cw20ExecuteMsg {
+        Transfer { recipient: String, amount: Uint128 },
+    }
+
+    //Builds the payout message for `amount` of an auction's bid asset, whether that's
+    //native coins or a cw20 token.
+    fn payment_msg(
+        bid_asset: &BidAsset,
+        recipient: String,
+        amount: Uint128,
+    ) -> Result<CosmosMsg, ContractError> {
+        let msg = match bid_asset {
+            BidAsset::Native { denom } => BankMsg::Send {
+                to_address: recipient,
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }
+            .into(),
+            BidAsset::Cw20 { contract } => WasmMsg::Execute {
+                contract_addr: contract.clone().into_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+                funds: vec![],
+            }
+            .into(),
+        };
 
-    use crate::{error::ContractError, state::OWNERS};
+        Ok(msg)
+    }
+
+    fn record_auction(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        seller: Addr,
+        item: AuctionItem,
+        bid_asset: BidAsset,
+        kind: AuctionKind,
+        duration_secs: u64,
+    ) -> Result<u64, ContractError> {
+        if let AuctionKind::Dutch {
+            start_price,
+            floor_price,
+            ..
+        } = &kind
+        {
+            if floor_price > start_price {
+                return Err(ContractError::InvalidDutchPricing {
+                    start_price: *start_price,
+                    floor_price: *floor_price,
+                });
+            }
+        }
+
+        let id = AUCTION_SEQ.update(deps.storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+
+        let auction = Auction {
+            id,
+            seller,
+            item,
+            bid_asset,
+            kind,
+            end_time: env.block.time.plus_seconds(duration_secs),
+            high_bidder: None,
+            high_bid: None,
+            closed: false,
+        };
+
+        auctions().save(deps.storage, id, &auction)?;
+
+        Ok(id)
+    }
+
+    //Creates a new auction for the off-chain `item_id`; no asset is escrowed for the item
+    //itself. `duration_secs` is measured from the current block time (`env.block.time`).
+    pub fn create_auction(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        seller: Addr,
+        item_id: String,
+        bid_asset: BidAsset,
+        kind: AuctionKind,
+        duration_secs: u64,
+    ) -> ArchwayResult<ContractError> {
+        deps.api.addr_validate(&seller.clone().into_string())?;
+
+        if let BidAsset::Cw20 { contract } = &bid_asset {
+            deps.api.addr_validate(contract.as_str())?;
+        }
+
+        let id = record_auction(
+            deps,
+            env,
+            seller,
+            AuctionItem::Native { item_id },
+            bid_asset,
+            kind,
+            duration_secs,
+        )?;
+
+        let res = Response::new()
+            .add_attribute("method", "create_auction")
+            .add_attribute("auction_id", id.to_string());
+
+        Ok(res)
+    }
+
+    //Handles the `Cw721ReceiveMsg` a cw721 contract sends us when a seller calls `SendNft`,
+    //opening an auction for the escrowed token using the parameters carried in `msg`.
+    pub fn receive_nft(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        info: MessageInfo,
+        wrapper: Cw721ReceiveMsg,
+    ) -> ArchwayResult<ContractError> {
+        let seller = deps.api.addr_validate(&wrapper.sender)?;
+        let create_msg: CreateAuctionMsg = from_binary(&wrapper.msg)?;
+
+        if let BidAsset::Cw20 { contract } = &create_msg.bid_asset {
+            deps.api.addr_validate(contract.as_str())?;
+        }
+
+        let id = record_auction(
+            deps,
+            env,
+            seller,
+            AuctionItem::Nft {
+                contract: info.sender,
+                token_id: wrapper.token_id,
+            },
+            create_msg.bid_asset,
+            create_msg.kind,
+            create_msg.duration_secs,
+        )?;
+
+        let res = Response::new()
+            .add_attribute("method", "receive_nft")
+            .add_attribute("auction_id", id.to_string());
+
+        Ok(res)
+    }
+
+    //Applies a bid to an auction, refunding whoever it displaces as the high bidder.
+    //Shared by the native `place_bid` entry point and the cw20 `receive` hook.
+    fn apply_bid(
+        auction: &mut Auction,
+        env: &Env,
+        bidder: Addr,
+        bid: Uint128,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
+        let auction_id = auction.id;
+
+        if auction.closed {
+            return Err(ContractError::AuctionAlreadyClosed { auction_id });
+        }
+
+        if env.block.time >= auction.end_time {
+            return Err(ContractError::AuctionEnded { auction_id });
+        }
+
+        if let AuctionKind::Dutch { start_time, .. } = &auction.kind {
+            if env.block.time < *start_time {
+                return Err(ContractError::AuctionNotStarted { auction_id });
+            }
+        }
+
+        let required = required_bid(auction, env.block.time)?;
+
+        if bid < required {
+            return Err(ContractError::BidTooLow { bid, required });
+        }
+
+        let mut messages = Vec::new();
+
+        if let (Some(prev_bidder), Some(prev_bid)) = (auction.high_bidder.clone(), auction.high_bid)
+        {
+            messages.push(payment_msg(
+                &auction.bid_asset,
+                prev_bidder.into_string(),
+                prev_bid,
+            )?);
+        }
+
+        //A Dutch lot is won the instant a bid clears the live asking price, so it settles at
+        //that quoted price rather than whatever the bidder happened to send; refund the
+        //difference instead of letting it vanish into the seller/fee payout.
+        let accepted = if matches!(auction.kind, AuctionKind::Dutch { .. }) {
+            if bid > required {
+                messages.push(payment_msg(
+                    &auction.bid_asset,
+                    bidder.clone().into_string(),
+                    bid - required,
+                )?);
+            }
+            required
+        } else {
+            bid
+        };
+
+        auction.high_bidder = Some(bidder);
+        auction.high_bid = Some(accepted);
+
+        Ok(messages)
+    }
+
+    //Places a native-coin bid on an open auction, escrowing the sent funds until they are
+    //either returned (outbid) or paid out at settlement.
+    pub fn place_bid(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> ArchwayResult<ContractError> {
+        let mut auction = auctions()
+            .load(deps.storage, auction_id)
+            .map_err(|_| ContractError::AuctionNotFound { auction_id })?;
+
+        let denom = match &auction.bid_asset {
+            BidAsset::Native { denom } => denom.clone(),
+            BidAsset::Cw20 { .. } => return Err(ContractError::WrongBidAsset { auction_id }),
+        };
+
+        if info.funds.len() != 1 || info.funds[0].denom != denom {
+            return Err(ContractError::InvalidFunds { denom });
+        }
+
+        let bid = info.funds[0].amount;
+        let is_dutch = matches!(auction.kind, AuctionKind::Dutch { .. });
+        let refunds = apply_bid(&mut auction, &env, info.sender.clone(), bid)?;
+
+        let mut res = Response::new()
+            .add_attribute("method", "place_bid")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("bidder", info.sender)
+            .add_attribute("amount", bid.to_string())
+            .add_messages(refunds);
+
+        if is_dutch {
+            let settle_res = finalize_auction(deps, auction)?;
+            res = res
+                .add_attributes(settle_res.attributes)
+                .add_messages(settle_res.messages);
+        } else {
+            auctions().save(deps.storage, auction_id, &auction)?;
+        }
+
+        Ok(res)
+    }
+
+    //Handles the `Cw20ReceiveMsg` a cw20 contract sends us when a bidder calls `Send`,
+    //applying the transferred `amount` as a bid on the auction named in `msg`.
+    pub fn receive(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        info: MessageInfo,
+        wrapper: Cw20ReceiveMsg,
+    ) -> ArchwayResult<ContractError> {
+        let bidder = deps.api.addr_validate(&wrapper.sender)?;
+        let bid_msg: PlaceBidMsg = from_binary(&wrapper.msg)?;
+        let auction_id = bid_msg.auction_id;
+
+        let mut auction = auctions()
+            .load(deps.storage, auction_id)
+            .map_err(|_| ContractError::AuctionNotFound { auction_id })?;
+
+        match &auction.bid_asset {
+            BidAsset::Cw20 { contract } if *contract == info.sender => {}
+            _ => return Err(ContractError::WrongBidAsset { auction_id }),
+        }
+
+        let is_dutch = matches!(auction.kind, AuctionKind::Dutch { .. });
+        let refunds = apply_bid(&mut auction, &env, bidder, wrapper.amount)?;
+
+        let mut res = Response::new()
+            .add_attribute("method", "receive")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("amount", wrapper.amount.to_string())
+            .add_messages(refunds);
+
+        if is_dutch {
+            let settle_res = finalize_auction(deps, auction)?;
+            res = res
+                .add_attributes(settle_res.attributes)
+                .add_messages(settle_res.messages);
+        } else {
+            auctions().save(deps.storage, auction_id, &auction)?;
+        }
+
+        Ok(res)
+    }
+
+    //Closes out an auction once its end time has passed: pays the seller the winning bid
+    //minus the house fee, splits that fee equally among `OWNERS`, and returns the item to
+    //the seller if nobody bid.
+    pub fn settle_auction(
+        deps: DepsMut<ArchwayQuery>,
+        env: Env,
+        auction_id: u64,
+    ) -> ArchwayResult<ContractError> {
+        let auction = auctions()
+            .load(deps.storage, auction_id)
+            .map_err(|_| ContractError::AuctionNotFound { auction_id })?;
+
+        if auction.closed {
+            return Err(ContractError::AuctionAlreadyClosed { auction_id });
+        }
+
+        if env.block.time < auction.end_time {
+            return Err(ContractError::AuctionNotEnded { auction_id });
+        }
+
+        finalize_auction(deps, auction)
+    }
+
+    //Pays out a winning bid (or returns the item to the seller if there was none) and marks
+    //the auction closed. Shared by `settle_auction`, which enforces the usual end-time guard,
+    //and the Dutch instant-buy path in `place_bid`/`receive`, which settles the moment a bid
+    //clears the live asking price.
+    fn finalize_auction(
+        deps: DepsMut<ArchwayQuery>,
+        mut auction: Auction,
+    ) -> ArchwayResult<ContractError> {
+        let auction_id = auction.id;
+
+        let mut res = Response::new()
+            .add_attribute("method", "settle_auction")
+            .add_attribute("auction_id", auction_id.to_string());
+
+        let item_recipient = match (auction.high_bidder.clone(), auction.high_bid) {
+            (Some(winner), Some(winning_bid)) => {
+                let fee_bps = FEE_BPS.load(deps.storage)?;
+                let fee = winning_bid.multiply_ratio(fee_bps, 10_000u128);
+                let seller_amount = winning_bid.checked_sub(fee)?;
+
+                if !seller_amount.is_zero() {
+                    res = res.add_message(payment_msg(
+                        &auction.bid_asset,
+                        auction.seller.clone().into_string(),
+                        seller_amount,
+                    )?);
+                }
+
+                let owners = OWNERS.load(deps.storage)?;
+                if !fee.is_zero() && !owners.is_empty() {
+                    let share = fee.multiply_ratio(1u128, owners.len() as u128);
+                    if !share.is_zero() {
+                        for owner in owners {
+                            res = res.add_message(payment_msg(
+                                &auction.bid_asset,
+                                owner.into_string(),
+                                share,
+                            )?);
+                        }
+                    }
+                }
+
+                winner
+            }
+            _ => {
+                res = res.add_attribute("result", "no_bids");
+                auction.seller.clone()
+            }
+        };
+
+        if let AuctionItem::Nft { contract, token_id } = &auction.item {
+            res = res.add_message(WasmMsg::Execute {
+                contract_addr: contract.clone().into_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: item_recipient.into_string(),
+                    token_id: token_id.clone(),
+                })?,
+                funds: vec![],
+            });
+        }
+
+        auction.closed = true;
+
+        auctions().save(deps.storage, auction_id, &auction)?;
+
+        Ok(res)
+    }
 
     pub fn update_rewards_address(
         deps: DepsMut<ArchwayQuery>,
@@ -186,4 +734,461 @@ pub mod exec {
 
         Ok(res)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use std::marker::PhantomData;
+
+        use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+        use cosmwasm_std::{coins, OwnedDeps, Timestamp};
+
+        use super::*;
+
+        fn mock_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier, ArchwayQuery> {
+            OwnedDeps {
+                storage: MockStorage::default(),
+                api: MockApi::default(),
+                querier: MockQuerier::default(),
+                custom_query_type: PhantomData,
+            }
+        }
+
+        fn seed_auction(deps: DepsMut<ArchwayQuery>, kind: AuctionKind, end_time: Timestamp) {
+            OWNERS
+                .save(deps.storage, &vec![Addr::unchecked("owner")])
+                .unwrap();
+            FEE_BPS.save(deps.storage, &500).unwrap();
+            AUCTION_SEQ.save(deps.storage, &1).unwrap();
+
+            let auction = Auction {
+                id: 1,
+                seller: Addr::unchecked("seller"),
+                item: AuctionItem::Native {
+                    item_id: "lot-1".to_string(),
+                },
+                bid_asset: BidAsset::Native {
+                    denom: "uarch".to_string(),
+                },
+                kind,
+                end_time,
+                high_bidder: None,
+                high_bid: None,
+                closed: false,
+            };
+            auctions().save(deps.storage, auction.id, &auction).unwrap();
+        }
+
+        #[test]
+        fn place_bid_happy_path_english() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                env.block.time.plus_seconds(100),
+            );
+
+            let info = mock_info("bidder", &coins(100, "uarch"));
+            let res = place_bid(deps.as_mut(), env, info, 1).unwrap();
+            assert!(res.messages.is_empty());
+
+            let auction = auctions().load(deps.as_ref().storage, 1).unwrap();
+            assert_eq!(auction.high_bid, Some(Uint128::new(100)));
+            assert_eq!(auction.high_bidder, Some(Addr::unchecked("bidder")));
+            assert!(!auction.closed);
+        }
+
+        #[test]
+        fn settle_auction_before_end_time_is_rejected() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                env.block.time.plus_seconds(100),
+            );
+
+            let err = settle_auction(deps.as_mut(), env, 1).unwrap_err();
+            assert!(matches!(
+                err,
+                ContractError::AuctionNotEnded { auction_id: 1 }
+            ));
+        }
+
+        #[test]
+        fn settle_auction_pays_seller_and_fee_split() {
+            let mut deps = mock_deps();
+            let mut env = mock_env();
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                env.block.time.plus_seconds(100),
+            );
+
+            let info = mock_info("bidder", &coins(100, "uarch"));
+            place_bid(deps.as_mut(), env.clone(), info, 1).unwrap();
+
+            env.block.time = env.block.time.plus_seconds(101);
+            let res = settle_auction(deps.as_mut(), env.clone(), 1).unwrap();
+            //One payout to the seller and one to the sole owner's fee share.
+            assert_eq!(res.messages.len(), 2);
+
+            let auction = auctions().load(deps.as_ref().storage, 1).unwrap();
+            assert!(auction.closed);
+
+            let err = settle_auction(deps.as_mut(), env, 1).unwrap_err();
+            assert!(matches!(
+                err,
+                ContractError::AuctionAlreadyClosed { auction_id: 1 }
+            ));
+        }
+
+        #[test]
+        fn dutch_bid_settles_instantly_and_refunds_overpayment() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            let start_time = env.block.time.minus_seconds(10);
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::Dutch {
+                    start_price: Uint128::new(1_000),
+                    floor_price: Uint128::new(100),
+                    start_time,
+                },
+                env.block.time.plus_seconds(990),
+            );
+
+            //The quoted price has decayed below the 1000 sent, so the bidder overpays and
+            //should be refunded the difference as part of the same response.
+            let info = mock_info("bidder", &coins(1_000, "uarch"));
+            let res = place_bid(deps.as_mut(), env, info, 1).unwrap();
+            //Overpayment refund + seller payout + fee-share payout.
+            assert_eq!(res.messages.len(), 3);
+
+            let auction = auctions().load(deps.as_ref().storage, 1).unwrap();
+            assert!(auction.closed);
+            assert!(auction.high_bid.unwrap() < Uint128::new(1_000));
+        }
+
+        #[test]
+        fn closed_dutch_auction_rejects_a_second_bid() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            let start_time = env.block.time.minus_seconds(10);
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::Dutch {
+                    start_price: Uint128::new(1_000),
+                    floor_price: Uint128::new(100),
+                    start_time,
+                },
+                env.block.time.plus_seconds(990),
+            );
+
+            let first = mock_info("first-bidder", &coins(1_000, "uarch"));
+            place_bid(deps.as_mut(), env.clone(), first, 1).unwrap();
+
+            let second = mock_info("second-bidder", &coins(1_000, "uarch"));
+            let err = place_bid(deps.as_mut(), env, second, 1).unwrap_err();
+            assert!(matches!(
+                err,
+                ContractError::AuctionAlreadyClosed { auction_id: 1 }
+            ));
+        }
+
+        fn seed_cw20_auction(
+            deps: DepsMut<ArchwayQuery>,
+            cw20_contract: &str,
+            end_time: Timestamp,
+        ) {
+            OWNERS
+                .save(deps.storage, &vec![Addr::unchecked("owner")])
+                .unwrap();
+            FEE_BPS.save(deps.storage, &500).unwrap();
+            AUCTION_SEQ.save(deps.storage, &1).unwrap();
+
+            let auction = Auction {
+                id: 1,
+                seller: Addr::unchecked("seller"),
+                item: AuctionItem::Native {
+                    item_id: "lot-1".to_string(),
+                },
+                bid_asset: BidAsset::Cw20 {
+                    contract: Addr::unchecked(cw20_contract),
+                },
+                kind: AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                end_time,
+                high_bidder: None,
+                high_bid: None,
+                closed: false,
+            };
+            auctions().save(deps.storage, auction.id, &auction).unwrap();
+        }
+
+        #[test]
+        fn receive_accepts_a_bid_from_the_configured_cw20_contract() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            seed_cw20_auction(
+                deps.as_mut(),
+                "cw20-token",
+                env.block.time.plus_seconds(100),
+            );
+
+            let wrapper = Cw20ReceiveMsg {
+                sender: "bidder".to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&PlaceBidMsg { auction_id: 1 }).unwrap(),
+            };
+            let info = mock_info("cw20-token", &[]);
+            let res = receive(deps.as_mut(), env, info, wrapper).unwrap();
+            assert!(res.messages.is_empty());
+
+            let auction = auctions().load(deps.as_ref().storage, 1).unwrap();
+            assert_eq!(auction.high_bid, Some(Uint128::new(100)));
+            assert_eq!(auction.high_bidder, Some(Addr::unchecked("bidder")));
+        }
+
+        #[test]
+        fn receive_rejects_a_bid_sent_by_a_different_cw20_contract() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            seed_cw20_auction(
+                deps.as_mut(),
+                "cw20-token",
+                env.block.time.plus_seconds(100),
+            );
+
+            let wrapper = Cw20ReceiveMsg {
+                sender: "bidder".to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&PlaceBidMsg { auction_id: 1 }).unwrap(),
+            };
+            let info = mock_info("some-other-token", &[]);
+            let err = receive(deps.as_mut(), env, info, wrapper).unwrap_err();
+            assert!(matches!(
+                err,
+                ContractError::WrongBidAsset { auction_id: 1 }
+            ));
+        }
+
+        #[test]
+        fn receive_refunds_the_previous_bidder_in_cw20_on_outbid() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            seed_cw20_auction(
+                deps.as_mut(),
+                "cw20-token",
+                env.block.time.plus_seconds(100),
+            );
+
+            let first = Cw20ReceiveMsg {
+                sender: "first-bidder".to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&PlaceBidMsg { auction_id: 1 }).unwrap(),
+            };
+            receive(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("cw20-token", &[]),
+                first,
+            )
+            .unwrap();
+
+            let second = Cw20ReceiveMsg {
+                sender: "second-bidder".to_string(),
+                amount: Uint128::new(110),
+                msg: to_binary(&PlaceBidMsg { auction_id: 1 }).unwrap(),
+            };
+            let res = receive(deps.as_mut(), env, mock_info("cw20-token", &[]), second).unwrap();
+
+            assert_eq!(res.messages.len(), 1);
+            let expected_refund = to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "first-bidder".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap();
+            match &res.messages[0].msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    assert_eq!(contract_addr, "cw20-token");
+                    assert_eq!(msg, &expected_refund);
+                }
+                _ => panic!("expected a cw20 transfer message"),
+            }
+        }
+
+        #[test]
+        fn dutch_bid_before_start_time_is_rejected() {
+            let mut deps = mock_deps();
+            let env = mock_env();
+            let start_time = env.block.time.plus_seconds(100);
+            seed_auction(
+                deps.as_mut(),
+                AuctionKind::Dutch {
+                    start_price: Uint128::new(1_000),
+                    floor_price: Uint128::new(100),
+                    start_time,
+                },
+                env.block.time.plus_seconds(200),
+            );
+
+            let info = mock_info("bidder", &coins(1_000, "uarch"));
+            let err = place_bid(deps.as_mut(), env, info, 1).unwrap_err();
+            assert!(matches!(
+                err,
+                ContractError::AuctionNotStarted { auction_id: 1 }
+            ));
+        }
+
+        fn seed_nft_auction(deps: DepsMut<ArchwayQuery>, end_time: Timestamp) {
+            OWNERS
+                .save(deps.storage, &vec![Addr::unchecked("owner")])
+                .unwrap();
+            FEE_BPS.save(deps.storage, &500).unwrap();
+            AUCTION_SEQ.save(deps.storage, &1).unwrap();
+
+            let auction = Auction {
+                id: 1,
+                seller: Addr::unchecked("seller"),
+                item: AuctionItem::Nft {
+                    contract: Addr::unchecked("nft-contract"),
+                    token_id: "token-1".to_string(),
+                },
+                bid_asset: BidAsset::Native {
+                    denom: "uarch".to_string(),
+                },
+                kind: AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                end_time,
+                high_bidder: None,
+                high_bid: None,
+                closed: false,
+            };
+            auctions().save(deps.storage, auction.id, &auction).unwrap();
+        }
+
+        #[test]
+        fn receive_nft_opens_an_auction_for_the_escrowed_token() {
+            let mut deps = mock_deps();
+            OWNERS
+                .save(deps.as_mut().storage, &vec![Addr::unchecked("owner")])
+                .unwrap();
+            FEE_BPS.save(deps.as_mut().storage, &500).unwrap();
+            AUCTION_SEQ.save(deps.as_mut().storage, &0).unwrap();
+
+            let env = mock_env();
+            let info = mock_info("nft-contract", &[]);
+            let create_msg = CreateAuctionMsg {
+                bid_asset: BidAsset::Native {
+                    denom: "uarch".to_string(),
+                },
+                kind: AuctionKind::English {
+                    reserve_price: Uint128::new(100),
+                    min_increment: Uint128::new(10),
+                },
+                duration_secs: 100,
+            };
+            let wrapper = Cw721ReceiveMsg {
+                sender: "seller".to_string(),
+                token_id: "token-1".to_string(),
+                msg: to_binary(&create_msg).unwrap(),
+            };
+
+            let res = receive_nft(deps.as_mut(), env, info, wrapper).unwrap();
+            assert_eq!(
+                res.attributes
+                    .iter()
+                    .find(|a| a.key == "auction_id")
+                    .unwrap()
+                    .value,
+                "1"
+            );
+
+            let auction = auctions().load(deps.as_ref().storage, 1).unwrap();
+            assert_eq!(auction.seller, Addr::unchecked("seller"));
+            assert_eq!(
+                auction.item,
+                AuctionItem::Nft {
+                    contract: Addr::unchecked("nft-contract"),
+                    token_id: "token-1".to_string(),
+                }
+            );
+        }
+
+        #[test]
+        fn settle_auction_transfers_the_nft_to_the_winning_bidder() {
+            let mut deps = mock_deps();
+            let mut env = mock_env();
+            seed_nft_auction(deps.as_mut(), env.block.time.plus_seconds(100));
+
+            let info = mock_info("bidder", &coins(100, "uarch"));
+            place_bid(deps.as_mut(), env.clone(), info, 1).unwrap();
+
+            env.block.time = env.block.time.plus_seconds(101);
+            let res = settle_auction(deps.as_mut(), env, 1).unwrap();
+            //Seller payout + fee-share payout + the NFT transfer to the winner.
+            assert_eq!(res.messages.len(), 3);
+
+            let expected_transfer = to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: "bidder".to_string(),
+                token_id: "token-1".to_string(),
+            })
+            .unwrap();
+            match &res.messages[2].msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    assert_eq!(contract_addr, "nft-contract");
+                    assert_eq!(msg, &expected_transfer);
+                }
+                _ => panic!("expected a cw721 TransferNft message"),
+            }
+        }
+
+        #[test]
+        fn settle_auction_returns_the_nft_to_the_seller_when_there_were_no_bids() {
+            let mut deps = mock_deps();
+            let mut env = mock_env();
+            seed_nft_auction(deps.as_mut(), env.block.time.plus_seconds(100));
+
+            env.block.time = env.block.time.plus_seconds(101);
+            let res = settle_auction(deps.as_mut(), env, 1).unwrap();
+            assert_eq!(res.messages.len(), 1);
+            assert!(res
+                .attributes
+                .iter()
+                .any(|a| a.key == "result" && a.value == "no_bids"));
+
+            let expected_transfer = to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: "seller".to_string(),
+                token_id: "token-1".to_string(),
+            })
+            .unwrap();
+            match &res.messages[0].msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    assert_eq!(contract_addr, "nft-contract");
+                    assert_eq!(msg, &expected_transfer);
+                }
+                _ => panic!("expected a cw721 TransferNft message"),
+            }
+        }
+    }
 }
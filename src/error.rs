@@ -0,0 +1,49 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("At least one owner must remain")]
+    NoOwner,
+
+    #[error("Auction {auction_id} was not found")]
+    AuctionNotFound { auction_id: u64 },
+
+    #[error("Auction {auction_id} has already ended")]
+    AuctionEnded { auction_id: u64 },
+
+    #[error("Auction {auction_id} has not ended yet")]
+    AuctionNotEnded { auction_id: u64 },
+
+    #[error("Auction {auction_id} has not started yet")]
+    AuctionNotStarted { auction_id: u64 },
+
+    #[error("Auction {auction_id} was already settled")]
+    AuctionAlreadyClosed { auction_id: u64 },
+
+    #[error("fee_bps of {fee_bps} exceeds 10000 (100%)")]
+    InvalidFee { fee_bps: u64 },
+
+    #[error(
+        "Dutch auction floor_price of {floor_price} must not exceed start_price of {start_price}"
+    )]
+    InvalidDutchPricing {
+        start_price: Uint128,
+        floor_price: Uint128,
+    },
+
+    #[error("Bid must be sent as a single coin of denom {denom}")]
+    InvalidFunds { denom: String },
+
+    #[error("Auction {auction_id} does not accept bids in the asset provided")]
+    WrongBidAsset { auction_id: u64 },
+
+    #[error("Bid of {bid} does not exceed the required minimum of {required}")]
+    BidTooLow { bid: Uint128, required: Uint128 },
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::{Coin, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Auction, AuctionKind, BidAsset};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OpenAuctionsResp {
+    pub auctions: Vec<Auction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SellerAuctionsResp {
+    pub auctions: Vec<Auction>,
+}
+
+//The payload carried inside a `Cw721ReceiveMsg`'s `msg` field, describing the auction to
+//open for the escrowed NFT.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateAuctionMsg {
+    pub bid_asset: BidAsset,
+    pub kind: AuctionKind,
+    pub duration_secs: u64,
+}
+
+//The payload carried inside a `Cw20ReceiveMsg`'s `msg` field, identifying the auction a
+//cw20 token transfer is bidding on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlaceBidMsg {
+    pub auction_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OutstandingRewardsResponse {
+    pub rewards_balance: Vec<Coin>,
+    pub total_records: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrentPriceResponse {
+    pub price: Uint128,
+}
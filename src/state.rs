@@ -0,0 +1,251 @@
+use cosmwasm_std::{Addr, StdError, StdResult, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const OWNERS: Item<Vec<Addr>> = Item::new("owners");
+//Monotonically increasing counter used to mint auction ids as auctions are created.
+pub const AUCTION_SEQ: Item<u64> = Item::new("auction_seq");
+//House fee taken out of the winning bid at settlement, in basis points (1/100th of a percent).
+pub const FEE_BPS: Item<u64> = Item::new("fee_bps");
+
+pub struct AuctionIndexes<'a> {
+    pub seller: MultiIndex<'a, String, Auction, u64>,
+    //Indexed on whether the auction is still open, so `query::open_auctions` can page
+    //through only the auctions that matter without loading closed ones.
+    pub open: MultiIndex<'a, u8, Auction, u64>,
+}
+
+impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![&self.seller, &self.open];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn auctions<'a>() -> IndexedMap<'a, u64, Auction, AuctionIndexes<'a>> {
+    let indexes = AuctionIndexes {
+        seller: MultiIndex::new(
+            |_pk, auction| auction.seller.to_string(),
+            "auctions",
+            "auctions__seller",
+        ),
+        open: MultiIndex::new(
+            |_pk, auction| u8::from(!auction.closed),
+            "auctions",
+            "auctions__open",
+        ),
+    };
+    IndexedMap::new("auctions", indexes)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Addr,
+    pub item: AuctionItem,
+    pub bid_asset: BidAsset,
+    pub kind: AuctionKind,
+    pub end_time: Timestamp,
+    pub high_bidder: Option<Addr>,
+    pub high_bid: Option<Uint128>,
+    pub closed: bool,
+}
+
+//The lot being auctioned off: either a bare item id tracked off-chain, or an NFT this
+//contract is holding in escrow on behalf of the seller.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AuctionItem {
+    Native { item_id: String },
+    Nft { contract: Addr, token_id: String },
+}
+
+//The asset a bidder must pay in: native coins of a given denom, or a specific cw20 token.
+//Bound to an auction at creation time so mixed-asset auctions can coexist.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BidAsset {
+    Native { denom: String },
+    Cw20 { contract: Addr },
+}
+
+//The pricing mode an auction runs under: the standard ascending (English) format, or a
+//Dutch auction whose asking price decays linearly from `start_price` down to `floor_price`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AuctionKind {
+    English {
+        reserve_price: Uint128,
+        min_increment: Uint128,
+    },
+    Dutch {
+        start_price: Uint128,
+        floor_price: Uint128,
+        start_time: Timestamp,
+    },
+}
+
+//The bid an auction currently requires to win: for an English auction the reserve price
+//until the first bid and the next-increment price after it, or the live decayed price for a
+//Dutch one.
+pub fn required_bid(auction: &Auction, now: Timestamp) -> StdResult<Uint128> {
+    match &auction.kind {
+        AuctionKind::English {
+            reserve_price,
+            min_increment,
+        } => match auction.high_bid {
+            Some(high_bid) => high_bid.checked_add(*min_increment),
+            None => Ok(*reserve_price),
+        },
+        AuctionKind::Dutch {
+            start_price,
+            floor_price,
+            start_time,
+        } => dutch_current_price(
+            *start_price,
+            *floor_price,
+            *start_time,
+            auction.end_time,
+            now,
+        ),
+    }
+}
+
+//Linearly decays from `start_price` at `start_time` to `floor_price` at `end_time`, using
+//checked math throughout since these are user-supplied amounts. Clamped to zero before the
+//auction starts and saturated at the floor once it ends.
+pub fn dutch_current_price(
+    start_price: Uint128,
+    floor_price: Uint128,
+    start_time: Timestamp,
+    end_time: Timestamp,
+    now: Timestamp,
+) -> StdResult<Uint128> {
+    if now < start_time {
+        return Ok(Uint128::zero());
+    }
+    if now >= end_time {
+        return Ok(floor_price);
+    }
+
+    let elapsed = now
+        .seconds()
+        .checked_sub(start_time.seconds())
+        .ok_or_else(|| StdError::generic_err("dutch auction clock underflow"))?;
+    let duration = end_time
+        .seconds()
+        .checked_sub(start_time.seconds())
+        .ok_or_else(|| StdError::generic_err("dutch auction clock underflow"))?;
+
+    let decayed = start_price
+        .checked_sub(floor_price)?
+        .checked_mul(Uint128::from(elapsed))?
+        .checked_div(Uint128::from(duration))?;
+
+    Ok(start_price
+        .checked_sub(decayed)
+        .unwrap_or(floor_price)
+        .max(floor_price))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dutch_price_before_start_is_zero() {
+        let price = dutch_current_price(
+            Uint128::new(1_000),
+            Uint128::new(100),
+            Timestamp::from_seconds(100),
+            Timestamp::from_seconds(200),
+            Timestamp::from_seconds(50),
+        )
+        .unwrap();
+
+        assert_eq!(price, Uint128::zero());
+    }
+
+    #[test]
+    fn dutch_price_at_start_is_start_price() {
+        let price = dutch_current_price(
+            Uint128::new(1_000),
+            Uint128::new(100),
+            Timestamp::from_seconds(100),
+            Timestamp::from_seconds(200),
+            Timestamp::from_seconds(100),
+        )
+        .unwrap();
+
+        assert_eq!(price, Uint128::new(1_000));
+    }
+
+    #[test]
+    fn dutch_price_decays_linearly_mid_auction() {
+        let price = dutch_current_price(
+            Uint128::new(1_000),
+            Uint128::zero(),
+            Timestamp::from_seconds(0),
+            Timestamp::from_seconds(100),
+            Timestamp::from_seconds(25),
+        )
+        .unwrap();
+
+        assert_eq!(price, Uint128::new(750));
+    }
+
+    #[test]
+    fn dutch_price_at_end_is_floor_price() {
+        let price = dutch_current_price(
+            Uint128::new(1_000),
+            Uint128::new(100),
+            Timestamp::from_seconds(100),
+            Timestamp::from_seconds(200),
+            Timestamp::from_seconds(200),
+        )
+        .unwrap();
+
+        assert_eq!(price, Uint128::new(100));
+    }
+
+    #[test]
+    fn dutch_price_after_end_saturates_at_floor_price() {
+        let price = dutch_current_price(
+            Uint128::new(1_000),
+            Uint128::new(100),
+            Timestamp::from_seconds(100),
+            Timestamp::from_seconds(200),
+            Timestamp::from_seconds(9_999),
+        )
+        .unwrap();
+
+        assert_eq!(price, Uint128::new(100));
+    }
+
+    #[test]
+    fn required_bid_for_english_auction_uses_reserve_then_increment() {
+        let mut auction = Auction {
+            id: 1,
+            seller: Addr::unchecked("seller"),
+            item: AuctionItem::Native {
+                item_id: "lot-1".to_string(),
+            },
+            bid_asset: BidAsset::Native {
+                denom: "uarch".to_string(),
+            },
+            kind: AuctionKind::English {
+                reserve_price: Uint128::new(100),
+                min_increment: Uint128::new(10),
+            },
+            end_time: Timestamp::from_seconds(100),
+            high_bidder: None,
+            high_bid: None,
+            closed: false,
+        };
+
+        let now = Timestamp::from_seconds(0);
+        assert_eq!(required_bid(&auction, now).unwrap(), Uint128::new(100));
+
+        auction.high_bidder = Some(Addr::unchecked("bidder"));
+        auction.high_bid = Some(Uint128::new(100));
+        assert_eq!(required_bid(&auction, now).unwrap(), Uint128::new(110));
+    }
+}